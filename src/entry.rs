@@ -20,6 +20,7 @@ impl<'a, R, C, W> Clone for BorrowedEntry<'a, R, C, W> {
 impl<'a, R, C, W> Copy for BorrowedEntry<'a, R, C, W> {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OwnedEntry<R, C, W> {
     pub row: R,
     pub column: C,
@@ -1,5 +1,6 @@
 //! Iterator types.
 
+use crate::order::OrderedWeight;
 use std::hash::Hash;
 
 pub struct Iter<'a, R, C, W>
@@ -8,7 +9,7 @@ where
     C: Eq + Hash,
     W: Ord,
 {
-    pub(crate) iter: priority_queue::core_iterators::Iter<'a, (R, C), W>,
+    pub(crate) iter: priority_queue::core_iterators::Iter<'a, (R, C), OrderedWeight<W>>,
 }
 
 impl<'a, R, C, W> Iterator for Iter<'a, R, C, W>
@@ -20,8 +21,8 @@ where
     type Item = (&'a R, &'a C, &'a W);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ((row, col), weight) = self.iter.next()?;
-        Some((row, col, weight))
+        let ((row, col), ordered) = self.iter.next()?;
+        Some((row, col, &ordered.weight))
     }
 }
 
@@ -31,7 +32,7 @@ where
     C: Eq + Hash,
     W: Ord,
 {
-    pub(crate) iter: priority_queue::core_iterators::IntoIter<(R, C), W>,
+    pub(crate) iter: priority_queue::core_iterators::IntoIter<(R, C), OrderedWeight<W>>,
 }
 
 impl<R, C, W> Iterator for IntoIter<R, C, W>
@@ -43,7 +44,7 @@ where
     type Item = (R, C, W);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ((row, col), weight) = self.iter.next()?;
-        Some((row, col, weight))
+        let ((row, col), ordered) = self.iter.next()?;
+        Some((row, col, ordered.weight))
     }
 }
@@ -34,14 +34,26 @@
 
 pub mod entry;
 pub mod iter;
+mod order;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod snapshot;
 
 use crate::{
     entry::{BorrowedEntry, OwnedEntry},
     iter::{IntoIter, Iter},
+    order::OrderedWeight,
+    snapshot::{InverseOp, Snapshot},
 };
 use itertools::Itertools;
+use num_traits::Zero;
 use priority_queue::PriorityQueue;
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::{Add, Mul},
+};
 
 /// The 2-dimensional matrix that supports per-row and per-column
 /// maximum key queries.
@@ -52,9 +64,11 @@ where
     C: Clone + Eq + Hash,
     W: Clone + Ord,
 {
-    entries: PriorityQueue<(R, C), W>,
-    rows: HashMap<R, PriorityQueue<C, W>>,
-    cols: HashMap<C, PriorityQueue<R, W>>,
+    entries: PriorityQueue<(R, C), OrderedWeight<W>>,
+    rows: HashMap<R, PriorityQueue<C, OrderedWeight<W>>>,
+    cols: HashMap<C, PriorityQueue<R, OrderedWeight<W>>>,
+    journal: Vec<InverseOp<R, C, W>>,
+    min_first: bool,
 }
 
 impl<R, C, W> PriorityMatrix<R, C, W>
@@ -67,56 +81,296 @@ where
         Self::default()
     }
 
+    /// Builds a min-oriented matrix: `peek`/`pop`/`peek_from_row`/
+    /// `peek_from_column` (and their `_k` variants) return the *smallest*
+    /// weight instead of the largest. `new()`/`Default`/`FromIterator`
+    /// remain max-oriented.
+    pub fn new_min() -> Self {
+        Self {
+            entries: PriorityQueue::new(),
+            rows: HashMap::new(),
+            cols: HashMap::new(),
+            journal: Vec::new(),
+            min_first: true,
+        }
+    }
+
+    fn wrap(&self, weight: W) -> OrderedWeight<W> {
+        OrderedWeight {
+            weight,
+            min_first: self.min_first,
+        }
+    }
+
+    /// Builds a matrix from `(row, col, weight)` triples with a given
+    /// ordering, rebuilding the `rows`/`cols` indexes from scratch. Shared by
+    /// [`FromIterator::from_iter`] (always max-oriented) and the `serde`
+    /// support (which persists `min_first` so a deserialized matrix keeps
+    /// the ordering it was serialized with).
+    pub(crate) fn from_entries<T>(iter: T, min_first: bool) -> Self
+    where
+        T: IntoIterator<Item = (R, C, W)>,
+    {
+        let entries: PriorityQueue<(R, C), OrderedWeight<W>> = iter
+            .into_iter()
+            .map(|(row, col, val)| {
+                (
+                    (row, col),
+                    OrderedWeight {
+                        weight: val,
+                        min_first,
+                    },
+                )
+            })
+            .collect();
+        let rows: HashMap<R, PriorityQueue<C, OrderedWeight<W>>> = entries
+            .iter()
+            .map(|((row, col), iou)| (row.clone(), (col.clone(), iou.clone())))
+            .into_grouping_map()
+            .collect();
+        let cols: HashMap<C, PriorityQueue<R, OrderedWeight<W>>> = entries
+            .iter()
+            .map(|((row, col), iou)| (col.clone(), (row.clone(), iou.clone())))
+            .into_grouping_map()
+            .collect();
+
+        PriorityMatrix {
+            entries,
+            rows,
+            cols,
+            journal: Vec::new(),
+            min_first,
+        }
+    }
+
     pub fn insert(&mut self, row: R, col: C, weight: W) -> Option<W> {
+        let journal_row = row.clone();
+        let journal_col = col.clone();
+        let prev_weight = self.raw_insert(row, col, weight);
+        match &prev_weight {
+            Some(old) => self
+                .journal
+                .push(InverseOp::Insert(journal_row, journal_col, old.clone())),
+            None => self.journal.push(InverseOp::Remove(journal_row, journal_col)),
+        }
+        prev_weight
+    }
+
+    /// Inserts `(row, col, weight)` into all three indexes without touching
+    /// the undo journal. Used both by [`Self::insert`] and by [`Self::restore`]
+    /// when replaying a recorded inverse.
+    fn raw_insert(&mut self, row: R, col: C, weight: W) -> Option<W> {
+        let ordered = self.wrap(weight);
         let prev_weight = self
             .entries
-            .push((row.clone(), col.clone()), weight.clone());
+            .push((row.clone(), col.clone()), ordered.clone());
         self.rows
             .entry(row.clone())
             .or_insert_with(PriorityQueue::default)
-            .push(col.clone(), weight.clone());
+            .push(col.clone(), ordered.clone());
         self.cols
             .entry(col)
             .or_insert_with(PriorityQueue::default)
-            .push(row, weight);
-        prev_weight
+            .push(row, ordered);
+        prev_weight.map(|ordered| ordered.weight)
+    }
+
+    /// Removes `(row, col)` from all three indexes without touching the undo
+    /// journal. Used both by [`Self::remove`] and by [`Self::restore`] when
+    /// replaying a recorded inverse.
+    fn raw_remove(&mut self, row: &R, col: &C) -> Option<W> {
+        let (_, ordered) = self.entries.remove(&(row.clone(), col.clone()))?;
+        self.rows.get_mut(row).unwrap().remove(col);
+        prune_if_empty(&mut self.rows, row);
+        self.cols.get_mut(col).unwrap().remove(row);
+        prune_if_empty(&mut self.cols, col);
+        Some(ordered.weight)
+    }
+
+    /// Updates the weight of an existing cell in place, re-sifting it within
+    /// `entries`, `rows[row]` and `cols[col]` instead of removing and
+    /// re-inserting it. Returns the previous weight, or `None` if the cell
+    /// is absent.
+    pub fn change_weight(&mut self, row: &R, col: &C, weight: W) -> Option<W> {
+        let prev_weight = self.raw_change_weight(row, col, weight)?;
+        self.journal.push(InverseOp::ChangeWeight(
+            row.clone(),
+            col.clone(),
+            prev_weight.clone(),
+        ));
+        Some(prev_weight)
+    }
+
+    /// Changes the weight of an existing cell in place without touching the
+    /// undo journal. Used both by [`Self::change_weight`] and by
+    /// [`Self::restore`] when replaying a recorded inverse.
+    fn raw_change_weight(&mut self, row: &R, col: &C, weight: W) -> Option<W> {
+        let ordered = self.wrap(weight);
+        let prev_weight = self
+            .entries
+            .change_priority(&(row.clone(), col.clone()), ordered.clone())?;
+        self.rows
+            .get_mut(row)
+            .unwrap()
+            .change_priority(col, ordered.clone());
+        self.cols.get_mut(col).unwrap().change_priority(row, ordered);
+        Some(prev_weight.weight)
+    }
+
+    pub fn get<'a>(&'a self, row: &'a R, col: &'a C) -> Option<BorrowedEntry<'a, R, C, W>> {
+        let key = (row.clone(), col.clone());
+        let (_, ordered) = self.entries.get(&key)?;
+        Some(BorrowedEntry {
+            row,
+            column: col,
+            weight: &ordered.weight,
+        })
+    }
+
+    /// Returns an opaque checkpoint of the matrix's current state.
+    ///
+    /// Pass it to [`Self::restore`] to undo every `insert`/`remove`/`pop`
+    /// performed since this call.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            epoch: self.journal.len(),
+        }
+    }
+
+    /// Undoes every mutation recorded since `snap` was taken, replaying the
+    /// journaled inverse operations in reverse order.
+    pub fn restore(&mut self, snap: Snapshot) {
+        while self.journal.len() > snap.epoch {
+            match self.journal.pop().unwrap() {
+                InverseOp::Insert(row, col, weight) => {
+                    self.raw_insert(row, col, weight);
+                }
+                InverseOp::Remove(row, col) => {
+                    self.raw_remove(&row, &col);
+                }
+                InverseOp::ChangeWeight(row, col, weight) => {
+                    self.raw_change_weight(&row, &col, weight);
+                }
+            }
+        }
     }
 
     pub fn peek(&self) -> Option<BorrowedEntry<'_, R, C, W>> {
-        let ((row, col), weight) = self.entries.peek()?;
+        let ((row, col), ordered) = self.entries.peek()?;
         Some(BorrowedEntry {
             row,
             column: col,
-            weight,
+            weight: &ordered.weight,
         })
     }
 
     pub fn peek_from_row<'a>(&'a self, row: &'a R) -> Option<BorrowedEntry<'_, R, C, W>> {
         let (col, _) = self.rows.get(row)?.peek().unwrap();
         let key = (row.clone(), col.clone());
-        let (_, weight) = self.entries.get(&key).unwrap();
+        let (_, ordered) = self.entries.get(&key).unwrap();
         Some(BorrowedEntry {
             row,
             column: col,
-            weight,
+            weight: &ordered.weight,
         })
     }
 
     pub fn peek_from_column<'a>(&'a self, col: &'a C) -> Option<BorrowedEntry<'a, R, C, W>> {
         let (row, _) = self.cols.get(col)?.peek().unwrap();
         let key = (row.clone(), col.clone());
-        let (_, weight) = self.entries.get(&key).unwrap();
+        let (_, ordered) = self.entries.get(&key).unwrap();
         Some(BorrowedEntry {
             row,
             column: col,
-            weight,
+            weight: &ordered.weight,
         })
     }
 
+    pub fn peek_k_from_row<'a>(&'a self, row: &'a R, k: usize) -> Vec<BorrowedEntry<'a, R, C, W>> {
+        let queue = match self.rows.get(row) {
+            Some(queue) => queue,
+            None => return Vec::new(),
+        };
+        top_k(queue.iter(), k)
+            .into_iter()
+            .map(|(col, ordered)| BorrowedEntry {
+                row,
+                column: col,
+                weight: &ordered.weight,
+            })
+            .collect()
+    }
+
+    pub fn peek_k_from_column<'a>(
+        &'a self,
+        col: &'a C,
+        k: usize,
+    ) -> Vec<BorrowedEntry<'a, R, C, W>> {
+        let queue = match self.cols.get(col) {
+            Some(queue) => queue,
+            None => return Vec::new(),
+        };
+        top_k(queue.iter(), k)
+            .into_iter()
+            .map(|(row, ordered)| BorrowedEntry {
+                row,
+                column: col,
+                weight: &ordered.weight,
+            })
+            .collect()
+    }
+
+    pub fn pop_k_from_row(&mut self, row: &R, k: usize) -> Vec<OwnedEntry<R, C, W>> {
+        let cols: Vec<C> = self
+            .peek_k_from_row(row, k)
+            .into_iter()
+            .map(|entry| entry.column.clone())
+            .collect();
+
+        cols.into_iter()
+            .map(|col| {
+                let weight = self.raw_remove(row, &col).unwrap();
+                self.journal
+                    .push(InverseOp::Insert(row.clone(), col.clone(), weight.clone()));
+                OwnedEntry {
+                    row: row.clone(),
+                    column: col,
+                    weight,
+                }
+            })
+            .collect()
+    }
+
+    pub fn pop_k_from_column(&mut self, col: &C, k: usize) -> Vec<OwnedEntry<R, C, W>> {
+        let rows: Vec<R> = self
+            .peek_k_from_column(col, k)
+            .into_iter()
+            .map(|entry| entry.row.clone())
+            .collect();
+
+        rows.into_iter()
+            .map(|row| {
+                let weight = self.raw_remove(&row, col).unwrap();
+                self.journal
+                    .push(InverseOp::Insert(row.clone(), col.clone(), weight.clone()));
+                OwnedEntry {
+                    row,
+                    column: col.clone(),
+                    weight,
+                }
+            })
+            .collect()
+    }
+
     pub fn pop(&mut self) -> Option<OwnedEntry<R, C, W>> {
-        let ((row, col), weight) = self.entries.pop()?;
-        self.rows.get_mut(&row).unwrap().remove(&col);
+        let ((row, col), ordered) = self.entries.pop()?;
         self.rows.get_mut(&row).unwrap().remove(&col);
+        prune_if_empty(&mut self.rows, &row);
+        self.cols.get_mut(&col).unwrap().remove(&row);
+        prune_if_empty(&mut self.cols, &col);
+        let weight = ordered.weight;
+        self.journal
+            .push(InverseOp::Insert(row.clone(), col.clone(), weight.clone()));
         Some(OwnedEntry {
             row,
             column: col,
@@ -125,10 +379,15 @@ where
     }
 
     pub fn pop_from_row(&mut self, row: &R) -> Option<OwnedEntry<R, C, W>> {
-        let (col, weight) = self.rows.get_mut(row)?.pop().unwrap();
+        let (col, ordered) = self.rows.get_mut(row)?.pop().unwrap();
         let key = (row.clone(), col.clone());
         self.entries.remove(&key);
+        prune_if_empty(&mut self.rows, row);
         self.cols.get_mut(&col).unwrap().remove(row);
+        prune_if_empty(&mut self.cols, &col);
+        let weight = ordered.weight;
+        self.journal
+            .push(InverseOp::Insert(row.clone(), col.clone(), weight.clone()));
         Some(OwnedEntry {
             row: row.clone(),
             column: col,
@@ -137,10 +396,15 @@ where
     }
 
     pub fn pop_from_column(&mut self, col: &C) -> Option<OwnedEntry<R, C, W>> {
-        let (row, weight) = self.cols.get_mut(col)?.pop().unwrap();
+        let (row, ordered) = self.cols.get_mut(col)?.pop().unwrap();
         let key = (row.clone(), col.clone());
         self.entries.remove(&key);
+        prune_if_empty(&mut self.cols, col);
         self.rows.get_mut(&row).unwrap().remove(col);
+        prune_if_empty(&mut self.rows, &row);
+        let weight = ordered.weight;
+        self.journal
+            .push(InverseOp::Insert(row.clone(), col.clone(), weight.clone()));
         Some(OwnedEntry {
             row,
             column: col.clone(),
@@ -149,13 +413,12 @@ where
     }
 
     pub fn remove(&mut self, row: &R, col: &C) -> bool {
-        let ok = self.entries.remove(&(row.clone(), col.clone())).is_some();
-        if !ok {
-            return false;
-        }
-
-        self.rows.get_mut(row).unwrap().remove(col);
-        self.cols.get_mut(col).unwrap().remove(row);
+        let weight = match self.raw_remove(row, col) {
+            Some(weight) => weight,
+            None => return false,
+        };
+        self.journal
+            .push(InverseOp::Insert(row.clone(), col.clone(), weight));
         true
     }
 
@@ -169,7 +432,10 @@ where
                 if let Some(queue) = self.cols.get_mut(&col) {
                     queue.remove(&row);
                 }
-                self.entries.remove(&(row, col));
+                prune_if_empty(&mut self.cols, &col);
+                if let Some((_, ordered)) = self.entries.remove(&(row.clone(), col.clone())) {
+                    self.journal.push(InverseOp::Insert(row, col, ordered.weight));
+                }
             });
     }
 
@@ -183,7 +449,10 @@ where
                 if let Some(queue) = self.rows.get_mut(&row) {
                     queue.remove(&col);
                 }
-                self.entries.remove(&(row, col));
+                prune_if_empty(&mut self.rows, &row);
+                if let Some((_, ordered)) = self.entries.remove(&(row.clone(), col.clone())) {
+                    self.journal.push(InverseOp::Insert(row, col, ordered.weight));
+                }
             });
     }
 
@@ -202,8 +471,10 @@ where
             .map(|(curr_row, _)| (curr_row, col.clone()));
         let all_keys = row_keys.chain(col_keys);
 
-        all_keys.for_each(|key| {
-            self.entries.remove(&key);
+        all_keys.for_each(|(row, col)| {
+            if let Some((_, ordered)) = self.entries.remove(&(row.clone(), col.clone())) {
+                self.journal.push(InverseOp::Insert(row, col, ordered.weight));
+            }
         });
     }
 
@@ -214,6 +485,63 @@ where
     }
 }
 
+impl<R, C, W> PriorityMatrix<R, C, W>
+where
+    R: Clone + Eq + Hash,
+    C: Clone + Eq + Hash,
+    W: Clone + Ord + Add<Output = W> + Zero,
+{
+    pub fn row_sum(&self, row: &R) -> Option<W> {
+        let queue = self.rows.get(row)?;
+        Some(
+            queue
+                .iter()
+                .fold(W::zero(), |acc, (_, ordered)| acc + ordered.weight.clone()),
+        )
+    }
+
+    pub fn column_sum(&self, col: &C) -> Option<W> {
+        let queue = self.cols.get(col)?;
+        Some(
+            queue
+                .iter()
+                .fold(W::zero(), |acc, (_, ordered)| acc + ordered.weight.clone()),
+        )
+    }
+
+    pub fn total(&self) -> W {
+        self.entries
+            .iter()
+            .fold(W::zero(), |acc, (_, ordered)| acc + ordered.weight.clone())
+    }
+}
+
+impl<R, C, W> PriorityMatrix<R, C, W>
+where
+    R: Clone + Eq + Hash,
+    C: Clone + Eq + Hash,
+    W: Clone + Ord + Add<Output = W> + Mul<Output = W> + Zero,
+{
+    /// Computes `self * vec`, i.e. for each populated row the sum of
+    /// `weight * vec[col]` over that row's stored columns. Only the
+    /// row/column pairs actually present in the sparse matrix are visited;
+    /// columns absent from `vec` contribute nothing.
+    pub fn matmul_vec(&self, vec: &HashMap<C, W>) -> HashMap<R, W> {
+        self.rows
+            .iter()
+            .map(|(row, queue)| {
+                let sum = queue.iter().fold(W::zero(), |acc, (col, ordered)| {
+                    match vec.get(col) {
+                        Some(v) => acc + ordered.weight.clone() * v.clone(),
+                        None => acc,
+                    }
+                });
+                (row.clone(), sum)
+            })
+            .collect()
+    }
+}
+
 impl<R, C, W> Default for PriorityMatrix<R, C, W>
 where
     R: Clone + Eq + Hash,
@@ -225,6 +553,8 @@ where
             entries: PriorityQueue::new(),
             rows: HashMap::new(),
             cols: HashMap::new(),
+            journal: Vec::new(),
+            min_first: false,
         }
     }
 }
@@ -239,27 +569,74 @@ where
     where
         T: IntoIterator<Item = (R, C, W)>,
     {
-        let entries: PriorityQueue<(R, C), W> = iter
-            .into_iter()
-            .map(|(row, col, val)| ((row, col), val))
-            .collect();
-        let rows: HashMap<R, PriorityQueue<C, W>> = entries
-            .iter()
-            .map(|((row, col), iou)| (row.clone(), (col.clone(), iou.clone())))
-            .into_grouping_map()
-            .collect();
-        let cols: HashMap<C, PriorityQueue<R, W>> = entries
-            .iter()
-            .map(|((row, col), iou)| (col.clone(), (row.clone(), iou.clone())))
-            .into_grouping_map()
-            .collect();
+        Self::from_entries(iter, false)
+    }
+}
 
-        PriorityMatrix {
-            entries,
-            rows,
-            cols,
+/// Drops `key`'s entry from a row/column index once its inner queue is
+/// empty, so a later lookup sees "no such row/column" instead of a stale
+/// queue with nothing to [`PriorityQueue::peek`].
+fn prune_if_empty<K, V, W>(index: &mut HashMap<K, PriorityQueue<V, OrderedWeight<W>>>, key: &K)
+where
+    K: Eq + Hash,
+    V: Hash + Eq,
+    W: Ord,
+{
+    if index.get(key).is_some_and(|queue| queue.is_empty()) {
+        index.remove(key);
+    }
+}
+
+/// Selects the `k` entries with the greatest weight out of an unordered
+/// iterator, using a bounded min-heap so the scratch space stays `O(k)`.
+fn top_k<'a, K, W>(iter: impl Iterator<Item = (&'a K, &'a W)>, k: usize) -> Vec<(&'a K, &'a W)>
+where
+    K: 'a,
+    W: Ord + 'a,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap: BinaryHeap<Reverse<HeapItem<'a, K, W>>> = BinaryHeap::with_capacity(k);
+    for (key, weight) in iter {
+        if heap.len() < k {
+            heap.push(Reverse(HeapItem { key, weight }));
+        } else if heap.peek().is_some_and(|Reverse(top)| weight > top.weight) {
+            heap.pop();
+            heap.push(Reverse(HeapItem { key, weight }));
         }
     }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(item)| (item.key, item.weight))
+        .collect()
+}
+
+struct HeapItem<'a, K, W> {
+    key: &'a K,
+    weight: &'a W,
+}
+
+impl<'a, K, W: PartialEq> PartialEq for HeapItem<'a, K, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight
+    }
+}
+
+impl<'a, K, W: Eq> Eq for HeapItem<'a, K, W> {}
+
+impl<'a, K, W: PartialOrd> PartialOrd for HeapItem<'a, K, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.weight.partial_cmp(other.weight)
+    }
+}
+
+impl<'a, K, W: Ord> Ord for HeapItem<'a, K, W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.weight.cmp(other.weight)
+    }
 }
 
 impl<R, C, W> IntoIterator for PriorityMatrix<R, C, W>
@@ -0,0 +1,42 @@
+//! Internal weight ordering support for [`crate::PriorityMatrix`].
+//!
+//! Every weight stored in the matrix's three `PriorityQueue`s is wrapped in
+//! [`OrderedWeight`], which carries a per-matrix `min_first` flag alongside
+//! the weight. This lets a single matrix type be built either max-oriented
+//! (the default, via [`crate::PriorityMatrix::new`]/[`Default`]) or
+//! min-oriented (via [`crate::PriorityMatrix::new_min`]) while the public
+//! API keeps dealing in bare `W` values.
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+pub(crate) struct OrderedWeight<W> {
+    pub(crate) weight: W,
+    pub(crate) min_first: bool,
+}
+
+impl<W: PartialEq> PartialEq for OrderedWeight<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight
+    }
+}
+
+impl<W: Eq> Eq for OrderedWeight<W> {}
+
+impl<W: PartialOrd> PartialOrd for OrderedWeight<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let ord = self.weight.partial_cmp(&other.weight)?;
+        Some(if self.min_first { ord.reverse() } else { ord })
+    }
+}
+
+impl<W: Ord> Ord for OrderedWeight<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ord = self.weight.cmp(&other.weight);
+        if self.min_first {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Optional [`serde`] support, gated behind the `serde` feature.
+//!
+//! A [`PriorityMatrix`] is serialized as a `(min_first, entries)` pair: the
+//! flat list of `(row, column, weight)` triples it was built from, plus the
+//! ordering flag set by [`PriorityMatrix::new`]/[`PriorityMatrix::new_min`].
+//! The `rows`/`cols` indexes are never serialized and are rebuilt from that
+//! list on deserialization via [`PriorityMatrix::from_entries`], so a
+//! deserialized matrix can never carry indexes that disagree with its
+//! entries, and it always keeps the ordering it was serialized with.
+
+use crate::PriorityMatrix;
+use serde::{
+    de::{Deserializer, SeqAccess, Visitor},
+    ser::{SerializeTuple, Serializer},
+    Deserialize, Serialize,
+};
+use std::{fmt, hash::Hash, marker::PhantomData};
+
+impl<R, C, W> Serialize for PriorityMatrix<R, C, W>
+where
+    R: Clone + Eq + Hash + Serialize,
+    C: Clone + Eq + Hash + Serialize,
+    W: Clone + Ord + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entries: Vec<(&R, &C, &W)> = self.iter().collect();
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.min_first)?;
+        tuple.serialize_element(&entries)?;
+        tuple.end()
+    }
+}
+
+impl<'de, R, C, W> Deserialize<'de> for PriorityMatrix<R, C, W>
+where
+    R: Clone + Eq + Hash + Deserialize<'de>,
+    C: Clone + Eq + Hash + Deserialize<'de>,
+    W: Clone + Ord + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MatrixVisitor<R, C, W>(PhantomData<(R, C, W)>);
+
+        impl<'de, R, C, W> Visitor<'de> for MatrixVisitor<R, C, W>
+        where
+            R: Clone + Eq + Hash + Deserialize<'de>,
+            C: Clone + Eq + Hash + Deserialize<'de>,
+            W: Clone + Ord + Deserialize<'de>,
+        {
+            type Value = PriorityMatrix<R, C, W>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a (min_first, entries) tuple")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let min_first = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                let entries: Vec<(R, C, W)> = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+                Ok(PriorityMatrix::from_entries(entries, min_first))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, MatrixVisitor(PhantomData))
+    }
+}
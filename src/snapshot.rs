@@ -0,0 +1,23 @@
+//! Snapshot/rollback support for [`crate::PriorityMatrix`].
+
+/// An opaque checkpoint returned by [`crate::PriorityMatrix::snapshot`].
+///
+/// Pass it to [`crate::PriorityMatrix::restore`] to undo every
+/// `insert`/`remove`/`pop` performed on the matrix since the snapshot was
+/// taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub(crate) epoch: usize,
+}
+
+/// The inverse of a single mutating operation, recorded in the matrix's
+/// journal so it can be replayed to undo that operation.
+#[derive(Debug, Clone)]
+pub(crate) enum InverseOp<R, C, W> {
+    /// Undo by re-inserting `(row, col, weight)`.
+    Insert(R, C, W),
+    /// Undo by removing `(row, col)`.
+    Remove(R, C),
+    /// Undo by changing `(row, col)`'s weight back to `weight`.
+    ChangeWeight(R, C, W),
+}
@@ -2,7 +2,7 @@ use priority_matrix::{
     entry::{BorrowedEntry, OwnedEntry},
     PriorityMatrix,
 };
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 #[test]
 fn insert_test() {
@@ -167,6 +167,271 @@ fn pop_column_test() {
     }
 }
 
+#[test]
+fn peek_k_from_row_test() {
+    let mat = init();
+
+    let entries = mat.peek_k_from_row(&'a', 2);
+    assert_eq!(entries.len(), 2);
+    check_bentry(entries[0], 'a', "beta", 3);
+    check_bentry(entries[1], 'a', "alpha", 0);
+
+    let entries = mat.peek_k_from_row(&'a', 10);
+    assert_eq!(entries.len(), 2);
+
+    assert!(mat.peek_k_from_row(&'z', 2).is_empty());
+}
+
+#[test]
+fn peek_k_from_column_test() {
+    let mat = init();
+
+    let entries = mat.peek_k_from_column(&"alpha", 2);
+    assert_eq!(entries.len(), 2);
+    check_bentry(entries[0], 'b', "alpha", 2);
+    check_bentry(entries[1], 'a', "alpha", 0);
+}
+
+#[test]
+fn pop_k_from_row_test() {
+    let mut mat = init();
+
+    let entries = mat.pop_k_from_row(&'a', 1);
+    assert_eq!(entries.len(), 1);
+    check_oentry(&entries[0], 'a', "beta", 3);
+
+    let entry = mat.peek_from_row(&'a').unwrap();
+    check_bentry(entry, 'a', "alpha", 0);
+
+    let entry = mat.peek_from_column(&"beta").unwrap();
+    check_bentry(entry, 'b', "beta", 1);
+}
+
+#[test]
+fn pop_k_from_column_test() {
+    let mut mat = init();
+
+    let entries = mat.pop_k_from_column(&"alpha", 2);
+    assert_eq!(entries.len(), 2);
+    check_oentry(&entries[0], 'b', "alpha", 2);
+    check_oentry(&entries[1], 'a', "alpha", 0);
+
+    assert!(mat.peek_from_column(&"alpha").is_none());
+    assert!(mat.peek_from_row(&'a').is_some());
+}
+
+#[test]
+fn row_and_column_sum_test() {
+    let mat = init();
+
+    assert_eq!(mat.row_sum(&'a'), Some(3));
+    assert_eq!(mat.row_sum(&'b'), Some(3));
+    assert_eq!(mat.row_sum(&'z'), None);
+
+    assert_eq!(mat.column_sum(&"alpha"), Some(2));
+    assert_eq!(mat.column_sum(&"beta"), Some(4));
+
+    assert_eq!(mat.total(), 6);
+}
+
+#[test]
+fn row_and_column_sum_after_emptying_test() {
+    let mut mat: PriorityMatrix<char, &str, i32> = [('a', "x", 1), ('b', "y", 2)]
+        .into_iter()
+        .collect();
+
+    mat.pop_from_row(&'a');
+
+    // Row 'a' no longer has any entries, so it must report as unpopulated
+    // rather than a phantom zero-weight row.
+    assert_eq!(mat.row_sum(&'a'), None);
+    assert_eq!(mat.column_sum(&"x"), None);
+    assert_eq!(mat.row_sum(&'b'), Some(2));
+
+    let vec = HashMap::from([("x", 1), ("y", 1)]);
+    let result = mat.matmul_vec(&vec);
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[&'b'], 2);
+}
+
+#[test]
+fn column_sum_after_pop_test() {
+    let mut mat: PriorityMatrix<char, &str, i32> = [('a', "alpha", 1), ('a', "beta", 100)]
+        .into_iter()
+        .collect();
+
+    // Pops the max entry, ('a', "beta", 100), emptying column "beta".
+    mat.pop();
+
+    assert_eq!(mat.column_sum(&"beta"), None);
+    assert_eq!(mat.row_sum(&'a'), Some(1));
+    assert!(mat.peek_from_column(&"beta").is_none());
+}
+
+#[test]
+fn matmul_vec_test() {
+    let mat = init();
+
+    let vec = HashMap::from([("alpha", 2), ("beta", 1)]);
+    let result = mat.matmul_vec(&vec);
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[&'a'], 0 * 2 + 3 * 1);
+    assert_eq!(result[&'b'], 2 * 2 + 1 * 1);
+}
+
+#[test]
+fn remove_row_and_column_prune_other_side_test() {
+    {
+        let mut mat: PriorityMatrix<char, &str, i32> =
+            [('a', "solo_col", 5)].into_iter().collect();
+        mat.remove_row(&'a');
+        assert!(mat.peek_from_column(&"solo_col").is_none());
+        assert_eq!(mat.column_sum(&"solo_col"), None);
+    }
+
+    {
+        let mut mat: PriorityMatrix<char, &str, i32> = [('s', "x", 5)].into_iter().collect();
+        mat.remove_column(&"x");
+        assert!(mat.peek_from_row(&'s').is_none());
+        assert_eq!(mat.row_sum(&'s'), None);
+    }
+}
+
+#[test]
+fn snapshot_restore_test() {
+    let mut mat = init();
+
+    let snap = mat.snapshot();
+
+    mat.insert('c', "gamma", 9);
+    mat.remove(&'a', &"alpha");
+    mat.pop_from_row(&'b');
+
+    assert_eq!(mat.row_sum(&'c'), Some(9));
+    assert_eq!(mat.row_sum(&'a'), Some(3));
+    assert_eq!(mat.row_sum(&'b'), Some(1));
+
+    mat.restore(snap);
+
+    assert_eq!(mat.row_sum(&'c'), None);
+    assert_eq!(mat.column_sum(&"gamma"), None);
+    assert_eq!(mat.row_sum(&'a'), Some(3));
+
+    let entry = mat.peek_from_row(&'a').unwrap();
+    check_bentry(entry, 'a', "beta", 3);
+    let entry = mat.peek_from_row(&'b').unwrap();
+    check_bentry(entry, 'b', "alpha", 2);
+
+    assert_eq!(mat.total(), 6);
+}
+
+#[test]
+fn get_test() {
+    let mat = init();
+
+    let entry = mat.get(&'a', &"alpha").unwrap();
+    check_bentry(entry, 'a', "alpha", 0);
+
+    assert!(mat.get(&'a', &"gamma").is_none());
+    assert!(mat.get(&'z', &"alpha").is_none());
+}
+
+#[test]
+fn change_weight_test() {
+    let mut mat = init();
+
+    let prev = mat.change_weight(&'a', &"alpha", 10);
+    assert_eq!(prev, Some(0));
+
+    let entry = mat.peek().unwrap();
+    check_bentry(entry, 'a', "alpha", 10);
+
+    let entry = mat.get(&'a', &"alpha").unwrap();
+    check_bentry(entry, 'a', "alpha", 10);
+
+    assert!(mat.change_weight(&'z', &"alpha", 1).is_none());
+}
+
+#[test]
+fn new_min_test() {
+    let mut mat = {
+        let mut mat = PriorityMatrix::new_min();
+        mat.insert('a', "alpha", 0);
+        mat.insert('a', "beta", 3);
+        mat.insert('b', "alpha", 2);
+        mat.insert('b', "beta", 1);
+        mat
+    };
+
+    {
+        let entry = mat.peek().unwrap();
+        check_bentry(entry, 'a', "alpha", 0);
+    }
+
+    {
+        let entry = mat.peek_from_row(&'b').unwrap();
+        check_bentry(entry, 'b', "beta", 1);
+    }
+
+    {
+        let entry = mat.peek_from_column(&"alpha").unwrap();
+        check_bentry(entry, 'a', "alpha", 0);
+    }
+
+    {
+        let entries = mat.peek_k_from_row(&'b', 2);
+        assert_eq!(entries.len(), 2);
+        check_bentry(entries[0], 'b', "beta", 1);
+        check_bentry(entries[1], 'b', "alpha", 2);
+    }
+
+    {
+        let entry = mat.pop().unwrap();
+        check_oentry(&entry, 'a', "alpha", 0);
+    }
+
+    {
+        let entry = mat.pop().unwrap();
+        check_oentry(&entry, 'b', "beta", 1);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip_preserves_min_first_test() {
+    let mut mat = PriorityMatrix::new_min();
+    mat.insert('a', "alpha", 0);
+    mat.insert('a', "beta", 3);
+
+    let json = serde_json::to_string(&mat).unwrap();
+    let restored: PriorityMatrix<char, &str, i32> = serde_json::from_str(&json).unwrap();
+
+    // A min-oriented matrix must stay min-oriented across a round trip.
+    let entry = restored.peek().unwrap();
+    check_bentry(entry, 'a', "alpha", 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip_test() {
+    let mat: PriorityMatrix<char, String, i32> = [
+        ('a', "alpha".to_string(), 0),
+        ('a', "beta".to_string(), 3),
+        ('b', "alpha".to_string(), 2),
+        ('b', "beta".to_string(), 1),
+    ]
+    .into_iter()
+    .collect();
+
+    let json = serde_json::to_string(&mat).unwrap();
+    let restored: PriorityMatrix<char, String, i32> = serde_json::from_str(&json).unwrap();
+
+    let entry = restored.peek().unwrap();
+    check_bentry(entry, 'a', "beta".to_string(), 3);
+    assert_eq!(restored.total(), 6);
+}
+
 fn check_bentry<R, C, W>(entry: BorrowedEntry<'_, R, C, W>, row: R, col: C, weight: W)
 where
     R: Debug + Eq,